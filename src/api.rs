@@ -0,0 +1,134 @@
+// Bitcoin Dev Kit
+// Written in 2020 by Alekos Filini <alekos.filini@gmail.com>
+//
+// Copyright (c) 2020-2021 Bitcoin Dev Kit Developers
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+//! Types returned by Esplora's HTTP API.
+
+use bitcoin::{BlockHash, Txid};
+use serde::Deserialize;
+
+/// Information about a specific address or scripthash, as returned by
+/// `/address/:address` or `/scripthash/:hash`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AddressStats {
+    pub address: Option<String>,
+    pub scripthash: Option<String>,
+    pub chain_stats: AddressTxsSummary,
+    pub mempool_stats: AddressTxsSummary,
+}
+
+/// Confirmed or mempool transaction counters for an address/scripthash.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct AddressTxsSummary {
+    pub funded_txo_count: u64,
+    pub funded_txo_sum: u64,
+    pub spent_txo_count: u64,
+    pub spent_txo_sum: u64,
+    pub tx_count: u64,
+}
+
+/// The confirmation status of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct TxStatus {
+    pub confirmed: bool,
+    pub block_height: Option<u32>,
+    pub block_hash: Option<BlockHash>,
+    pub block_time: Option<u64>,
+}
+
+/// Whether a block is part of the best chain, and what follows it.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct BlockStatus {
+    pub in_best_chain: bool,
+    pub height: Option<u32>,
+    pub next_best: Option<BlockHash>,
+}
+
+/// Summary fields for a block, as returned by `/blocks` and `/blocks/:height`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BlockSummary {
+    pub id: BlockHash,
+    pub height: u32,
+    pub version: i32,
+    pub timestamp: u64,
+    pub tx_count: u64,
+    pub size: u64,
+    pub weight: u64,
+    pub merkle_root: String,
+    pub previousblockhash: Option<BlockHash>,
+    pub mediantime: u64,
+    pub nonce: u32,
+    pub bits: u32,
+    pub difficulty: f64,
+}
+
+/// A merkle inclusion proof for a transaction.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MerkleProof {
+    pub block_height: u32,
+    pub merkle: Vec<String>,
+    pub pos: usize,
+}
+
+/// Whether a transaction output has been spent, and by what.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OutputStatus {
+    pub spent: bool,
+    pub txid: Option<Txid>,
+    pub vin: Option<u64>,
+    pub status: Option<TxStatus>,
+}
+
+/// A transaction, as returned by `/tx/:txid`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Tx {
+    pub txid: Txid,
+    pub version: i32,
+    pub locktime: u32,
+    pub size: u64,
+    pub weight: u64,
+    pub fee: u64,
+    pub status: TxStatus,
+}
+
+/// A snapshot of the mempool, as returned by Esplora's `/mempool` endpoint.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MempoolInfo {
+    /// Number of transactions currently in the mempool.
+    pub count: u64,
+    /// Total virtual size, in vbytes, of the mempool.
+    pub vsize: u64,
+    /// Total fees, in satoshis, of transactions in the mempool.
+    pub total_fee: u64,
+    /// Fee-rate histogram describing the backlog.
+    pub fee_histogram: Vec<FeeHistogramEntry>,
+}
+
+/// One bucket of the mempool fee-rate histogram: a feerate and the total
+/// vsize of mempool transactions in that bucket, i.e. paying at least that
+/// feerate but less than the previous (higher) entry's feerate.
+///
+/// Deserializes from Esplora's `[fee_rate, vsize]` two-element array.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct FeeHistogramEntry(pub f64, pub u64);
+
+impl FeeHistogramEntry {
+    /// The feerate, in sat/vB.
+    pub fn fee_rate(&self) -> f64 {
+        self.0
+    }
+
+    /// The total vsize, in vbytes, of mempool transactions in this bucket,
+    /// i.e. paying at least [`FeeHistogramEntry::fee_rate`] but less than the
+    /// previous entry's feerate.
+    pub fn vsize(&self) -> u64 {
+        self.1
+    }
+}
@@ -18,11 +18,11 @@ use bitcoin::Address;
 use bitcoin::{
     block::Header as BlockHeader, Block, BlockHash, MerkleBlock, Script, Transaction, Txid,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::marker::PhantomData;
 use std::str::FromStr;
 
-use crate::api::AddressStats;
+use crate::api::{AddressStats, FeeHistogramEntry, MempoolInfo};
 use crate::{
     BlockStatus, BlockSummary, Builder, Error, MerkleProof, OutputStatus, Tx, TxStatus,
     BASE_BACKOFF_MILLIS, RETRYABLE_ERROR_CODES,
@@ -31,12 +31,15 @@ use async_minreq::{Method, Request};
 #[allow(unused_imports)]
 use log::{debug, error, info, trace};
 
+use futures::future::join_all;
+use futures::stream::{self, Stream, StreamExt};
+
 #[derive(Debug, Clone)]
 pub struct AsyncClient<S = DefaultSleeper> {
     /// The URL of the Esplora Server.
     url: String,
-    /// Number of times to retry a request.
-    max_retries: usize,
+    /// Policy governing how failed requests are retried.
+    retry_policy: RetryPolicy,
     /// Default headers (applied to every request).
     headers: HashMap<String, String>,
     /// Marker for the sleeper.
@@ -48,7 +51,7 @@ impl<S: Sleeper> AsyncClient<S> {
     pub fn from_builder(builder: Builder) -> Result<Self, Error> {
         Ok(AsyncClient {
             url: builder.base_url,
-            max_retries: builder.max_retries,
+            retry_policy: builder.retry_policy,
             headers: builder.headers,
             marker: PhantomData,
         })
@@ -58,7 +61,7 @@ impl<S: Sleeper> AsyncClient<S> {
         AsyncClient {
             url,
             headers,
-            max_retries: crate::DEFAULT_MAX_RETRIES,
+            retry_policy: RetryPolicy::default(),
             marker: PhantomData,
         }
     }
@@ -249,26 +252,54 @@ impl<S: Sleeper> AsyncClient<S> {
     ///
     /// This function will return an error either from the HTTP client, or the
     /// [`bitcoin::consensus::Encodable`] serialization.
+    ///
+    /// Follows the same [`RetryPolicy`] as GET requests, except a retried
+    /// submit that turns out to have already been accepted (e.g. Esplora
+    /// reporting the transaction as already known to the mempool or already
+    /// confirmed) is treated as success rather than an error, since
+    /// resubmitting a valid transaction is idempotent.
     async fn post_request_hex<T: Encodable>(&self, path: &str, body: T) -> Result<(), Error> {
         let url = format!("{}{}", self.url, path);
         let body = serialize::<T>(&body).to_lower_hex_string();
 
-        let mut request = Request::new(Method::Post, &url).with_body(body);
-        for (key, value) in &self.headers {
-            request = request.with_header(key, value);
-        }
+        let mut attempt = 0u32;
+        let mut elapsed = std::time::Duration::ZERO;
 
-        let response = request.send().await.map_err(Error::AsyncMinreq)?;
-        if response.status_code > 299 {
-            return Err(Error::HttpResponse {
-                status: response.status_code as u16,
-                message: match response.as_str() {
-                    Ok(resp) => resp.to_string(),
-                    Err(_) => return Err(Error::InvalidResponse),
-                },
-            });
+        loop {
+            let mut request = Request::new(Method::Post, &url).with_body(body.clone());
+            for (key, value) in &self.headers {
+                request = request.with_header(key, value);
+            }
+
+            let response = request.send().await.map_err(Error::AsyncMinreq)?;
+            if response.status_code <= 299 {
+                return Ok(());
+            }
+
+            let message = match response.as_str() {
+                Ok(resp) => resp.to_string(),
+                Err(_) => return Err(Error::InvalidResponse),
+            };
+
+            if is_already_known_response(&message) {
+                return Ok(());
+            }
+
+            if !self
+                .retry_policy
+                .should_retry(attempt, response.status_code, elapsed)
+            {
+                return Err(Error::HttpResponse {
+                    status: response.status_code as u16,
+                    message,
+                });
+            }
+
+            let delay = self.retry_policy.delay_for_attempt(attempt);
+            S::sleep(delay).await;
+            elapsed += delay;
+            attempt += 1;
         }
-        Ok(())
     }
 
     /// Get a [`Transaction`] option given its [`Txid`]
@@ -359,6 +390,64 @@ impl<S: Sleeper> AsyncClient<S> {
         self.post_request_hex("/tx", transaction).await
     }
 
+    /// Block until `txid` reaches `confirmations` confirmations, returning
+    /// the confirming block's [`BlockStatus`].
+    ///
+    /// Polls [`AsyncClient::get_tx_status`] on an exponential-backoff loop
+    /// built on the same [`Sleeper`] used for HTTP retries, so callers don't
+    /// have to re-implement status polling themselves. If `timeout` elapses
+    /// before the target depth is reached, [`Error::Timeout`] is returned.
+    /// If the transaction drops out of the mempool without ever confirming
+    /// (its status goes from present to not found), [`Error::TransactionEvicted`]
+    /// is returned instead of polling forever. A 404 before the transaction
+    /// has ever been seen (e.g. right after [`AsyncClient::broadcast`],
+    /// before the backend's indexer has caught up) is treated as "not yet
+    /// visible" and retried rather than failing outright.
+    pub async fn wait_for_confirmation(
+        &self,
+        txid: &Txid,
+        confirmations: u32,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<BlockStatus, Error> {
+        let mut delay = BASE_BACKOFF_MILLIS;
+        let mut elapsed = std::time::Duration::ZERO;
+        let mut seen_in_mempool = false;
+
+        loop {
+            match self.get_tx_status(txid).await {
+                Ok(status) => match status.block_height {
+                    Some(block_height) => {
+                        let tip_height = self.get_height().await?;
+                        let depth = tip_height.saturating_sub(block_height) + 1;
+                        if depth >= confirmations {
+                            let block_hash = status.block_hash.ok_or(Error::InvalidResponse)?;
+                            return self.get_block_status(&block_hash).await;
+                        }
+                    }
+                    None => seen_in_mempool = true,
+                },
+                Err(Error::HttpResponse { status: 404, .. }) if seen_in_mempool => {
+                    return Err(Error::TransactionEvicted(*txid));
+                }
+                // Not yet visible to this backend's indexer, e.g. because we
+                // were called right after broadcast(): keep polling rather
+                // than failing outright.
+                Err(Error::HttpResponse { status: 404, .. }) => {}
+                Err(e) => return Err(e),
+            }
+
+            if let Some(timeout) = timeout {
+                if elapsed >= timeout {
+                    return Err(Error::Timeout);
+                }
+            }
+
+            S::sleep(delay).await;
+            elapsed += delay;
+            delay = (delay * 2).min(MAX_CONFIRMATION_BACKOFF);
+        }
+    }
+
     /// Get the current height of the blockchain tip
     pub async fn get_height(&self) -> Result<u32, Error> {
         self.get_response_text("/blocks/tip/height")
@@ -422,12 +511,70 @@ impl<S: Sleeper> AsyncClient<S> {
         self.get_response_json(&path).await
     }
 
+    /// Lazily stream every confirmed transaction for the specified address,
+    /// newest first.
+    ///
+    /// Internally refetches pages via [`AsyncClient::get_address_txs`],
+    /// feeding each page's last txid back in as `last_seen`, and stops once
+    /// a short page signals the end of history. HTTP errors are yielded as
+    /// `Err` items rather than ending the stream silently.
+    pub fn get_address_txs_stream<'a>(
+        &'a self,
+        address: &'a Address,
+    ) -> impl Stream<Item = Result<Tx, Error>> + 'a {
+        paginated_tx_stream(move |last_seen| self.get_address_txs(address, last_seen))
+    }
+
+    /// Lazily stream every confirmed transaction for the specified
+    /// scripthash, newest first.
+    ///
+    /// Internally refetches pages via [`AsyncClient::scripthash_txs`],
+    /// feeding each page's last txid back in as `last_seen`, and stops once
+    /// a short page signals the end of history. HTTP errors are yielded as
+    /// `Err` items rather than ending the stream silently.
+    pub fn scripthash_txs_stream<'a>(
+        &'a self,
+        script: &'a Script,
+    ) -> impl Stream<Item = Result<Tx, Error>> + 'a {
+        paginated_tx_stream(move |last_seen| self.scripthash_txs(script, last_seen))
+    }
+
     /// Get an map where the key is the confirmation target (in number of
     /// blocks) and the value is the estimated feerate (in sat/vB).
     pub async fn get_fee_estimates(&self) -> Result<HashMap<u16, f64>, Error> {
         self.get_response_json("/fee-estimates").await
     }
 
+    /// Get the estimated feerate (in sat/vB) for confirmation within
+    /// `target_blocks`, picked from [`AsyncClient::get_fee_estimates`].
+    ///
+    /// Esplora only publishes estimates for a fixed set of confirmation
+    /// targets, so this rounds up to the next higher published target when
+    /// `target_blocks` isn't an exact key, and falls back to the slowest
+    /// available estimate when `target_blocks` exceeds every published
+    /// target.
+    pub async fn estimate_fee(&self, target_blocks: u16) -> Result<f64, Error> {
+        let estimates = self.get_fee_estimates().await?;
+        pick_fee_estimate(&estimates, target_blocks).ok_or(Error::InvalidResponse)
+    }
+
+    /// Get a snapshot of the current mempool backlog: transaction count,
+    /// total virtual size and total fee, as returned by Esplora's
+    /// `/mempool` endpoint.
+    pub async fn get_mempool(&self) -> Result<MempoolInfo, Error> {
+        self.get_response_json("/mempool").await
+    }
+
+    /// Get the current mempool fee-rate histogram, as returned by Esplora's
+    /// `/mempool` endpoint.
+    ///
+    /// Each entry buckets a portion of the mempool backlog by feerate,
+    /// useful for RBF/CPFP logic that needs more detail than the point
+    /// estimates from [`AsyncClient::get_fee_estimates`].
+    pub async fn get_mempool_fee_histogram(&self) -> Result<Vec<FeeHistogramEntry>, Error> {
+        Ok(self.get_mempool().await?.fee_histogram)
+    }
+
     /// Gets some recent block summaries starting at the tip or at `height` if
     /// provided.
     ///
@@ -451,10 +598,10 @@ impl<S: Sleeper> AsyncClient<S> {
     }
 
     /// Sends a GET request to the given `url`, retrying failed attempts
-    /// for retryable error codes until max retries hit.
+    /// according to `self.retry_policy` until it gives up.
     async fn get_with_retry(&self, url: &str) -> Result<async_minreq::Response, Error> {
-        let mut delay = BASE_BACKOFF_MILLIS;
-        let mut attempts = 0;
+        let mut attempt = 0u32;
+        let mut elapsed = std::time::Duration::ZERO;
 
         loop {
             let mut request = Request::new(Method::Get, url);
@@ -462,14 +609,18 @@ impl<S: Sleeper> AsyncClient<S> {
                 request = request.with_header(key, value);
             }
 
-            match request.send().await? {
-                resp if attempts < self.max_retries && is_status_retryable(resp.status_code) => {
-                    S::sleep(delay).await;
-                    attempts += 1;
-                    delay *= 2;
-                }
-                resp => return Ok(resp),
+            let resp = request.send().await?;
+            if !self
+                .retry_policy
+                .should_retry(attempt, resp.status_code, elapsed)
+            {
+                return Ok(resp);
             }
+
+            let delay = self.retry_policy.delay_for_attempt(attempt);
+            S::sleep(delay).await;
+            elapsed += delay;
+            attempt += 1;
         }
     }
 }
@@ -478,6 +629,139 @@ fn is_status_retryable(status: i32) -> bool {
     RETRYABLE_ERROR_CODES.contains(&(status as u16))
 }
 
+/// Pure selection logic behind [`AsyncClient::estimate_fee`]: picks the
+/// estimate for the lowest published target that is still `>= target_blocks`,
+/// rounding up to the next higher published target when `target_blocks`
+/// isn't an exact key, and falling back to the slowest available estimate
+/// when `target_blocks` exceeds every published target.
+fn pick_fee_estimate(estimates: &HashMap<u16, f64>, target_blocks: u16) -> Option<f64> {
+    let target = estimates
+        .keys()
+        .copied()
+        .filter(|&target| target >= target_blocks)
+        .min()
+        .or_else(|| estimates.keys().copied().max())?;
+
+    estimates.get(&target).copied()
+}
+
+/// Returns true if an error response from the broadcast endpoint indicates
+/// the transaction was already accepted, making a retried submit a no-op
+/// success rather than a failure.
+fn is_already_known_response(message: &str) -> bool {
+    let lower = message.to_ascii_lowercase();
+    lower.contains("already")
+        && (lower.contains("mempool") || lower.contains("known") || lower.contains("block chain"))
+}
+
+/// Shared cursor logic behind [`AsyncClient::get_address_txs_stream`] and
+/// [`AsyncClient::scripthash_txs_stream`]: repeatedly call `fetch_page` with
+/// the last txid of the previous page, yielding each confirmed transaction
+/// in turn and stopping once a short page signals the end of history. HTTP
+/// errors are yielded as `Err` items rather than ending the stream silently.
+///
+/// The first page of `fetch_page(None)` may also contain unconfirmed
+/// mempool transactions (mixed in ahead of the confirmed ones); those are
+/// filtered out rather than yielded, since the cursor and page-size cutoff
+/// below are both defined in terms of confirmed transactions only.
+fn paginated_tx_stream<'a, F, Fut>(fetch_page: F) -> impl Stream<Item = Result<Tx, Error>> + 'a
+where
+    F: Fn(Option<Txid>) -> Fut + 'a,
+    Fut: std::future::Future<Output = Result<Vec<Tx>, Error>> + 'a,
+{
+    stream::unfold(Some(None), move |last_seen: Option<Option<Txid>>| {
+        let page = last_seen.map(&fetch_page);
+        async move {
+            match page?.await {
+                Ok(page) => {
+                    let confirmed: Vec<Tx> =
+                        page.into_iter().filter(|tx| tx.status.confirmed).collect();
+                    let last_txid = confirmed.last().map(|tx| tx.txid);
+                    let done = confirmed.len() < CONFIRMED_TXS_PAGE_SIZE;
+                    let next = if done { None } else { Some(last_txid) };
+                    let items: Vec<Result<Tx, Error>> = confirmed.into_iter().map(Ok).collect();
+                    Some((stream::iter(items), next))
+                }
+                Err(e) => Some((stream::iter(vec![Err(e)]), None)),
+            }
+        }
+    })
+    .flatten()
+}
+
+/// Controls how [`AsyncClient`] retries failed HTTP requests, for both
+/// reads and [`AsyncClient::broadcast`].
+///
+/// The delay before retry attempt `n` (0-indexed) is
+/// `base_backoff * multiplier^n`, capped at `max_backoff`, with up to
+/// `jitter` of additional random delay added on top to avoid many clients
+/// retrying in lockstep against the same Esplora instance. Retrying stops
+/// once `max_attempts` retries have been made, or once `max_elapsed` total
+/// time (if set) has passed, whichever comes first.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the initial attempt.
+    pub max_attempts: usize,
+    /// Delay before the first retry.
+    pub base_backoff: std::time::Duration,
+    /// Factor the delay is multiplied by after each retry.
+    pub multiplier: f64,
+    /// Upper bound on any single delay.
+    pub max_backoff: std::time::Duration,
+    /// Upper bound on the total time spent retrying, if any.
+    pub max_elapsed: Option<std::time::Duration>,
+    /// Maximum additional random delay added to each retry.
+    pub jitter: std::time::Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: crate::DEFAULT_MAX_RETRIES,
+            base_backoff: BASE_BACKOFF_MILLIS,
+            multiplier: 2.0,
+            max_backoff: std::time::Duration::from_secs(30),
+            max_elapsed: None,
+            jitter: std::time::Duration::ZERO,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether a response with `status_code`, having already made `attempt`
+    /// retries and spent `elapsed` time retrying, should be retried again.
+    fn should_retry(&self, attempt: u32, status_code: i32, elapsed: std::time::Duration) -> bool {
+        (attempt as usize) < self.max_attempts
+            && is_status_retryable(status_code)
+            && self.max_elapsed.map_or(true, |max| elapsed < max)
+    }
+
+    /// The delay to sleep before retry attempt `attempt` (0-indexed),
+    /// including jitter.
+    fn delay_for_attempt(&self, attempt: u32) -> std::time::Duration {
+        let scaled = self.base_backoff.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        let capped = scaled.min(self.max_backoff.as_secs_f64()).max(0.0);
+        let base = std::time::Duration::from_secs_f64(capped);
+
+        if self.jitter.is_zero() {
+            base
+        } else {
+            let jitter_millis = (self.jitter.as_millis() as u64).max(1);
+            let random = rand::random::<u64>() % jitter_millis;
+            base + std::time::Duration::from_millis(random)
+        }
+    }
+}
+
+/// Upper bound on the backoff delay used by [`AsyncClient::wait_for_confirmation`].
+const MAX_CONFIRMATION_BACKOFF: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Number of confirmed transactions Esplora returns per page from
+/// `/address/:address/txs/chain/:last_seen` and
+/// `/scripthash/:hash/txs/chain/:last_seen`. A shorter page means there's no
+/// more history to fetch.
+const CONFIRMED_TXS_PAGE_SIZE: usize = 25;
+
 pub trait Sleeper: 'static {
     type Sleep: std::future::Future<Output = ()>;
     fn sleep(dur: std::time::Duration) -> Self::Sleep;
@@ -494,3 +778,733 @@ impl Sleeper for DefaultSleeper {
         tokio::time::sleep(dur)
     }
 }
+
+/// Default number of blocks a [`TipPoller`] will walk backwards while
+/// searching for the common ancestor of a reorg before giving up.
+pub const DEFAULT_MAX_REORG_DEPTH: u32 = 100;
+
+/// Describes how the locally cached view of the best chain must change to
+/// match a newly observed tip.
+///
+/// `disconnected` lists blocks that are no longer part of the best chain,
+/// ordered from the previous tip down towards the common ancestor.
+/// `connected` lists the blocks that replace them, ordered from the common
+/// ancestor up to the new tip. Applying `disconnected` (in order) and then
+/// `connected` (in order) to the old tip yields the new tip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainDifference {
+    /// Blocks rolled back from the previously cached best chain, newest
+    /// first.
+    pub disconnected: Vec<BlockHash>,
+    /// Blocks newly added to the best chain, oldest first.
+    pub connected: Vec<BlockHeader>,
+}
+
+/// Polls an Esplora server for its chain tip and reports reorgs.
+///
+/// This is the block-source/SPV-client pattern applied to [`AsyncClient`]:
+/// `TipPoller` keeps a small rolling cache of recently seen headers. On each
+/// [`TipPoller::poll`] it fetches the server's current tip via
+/// [`AsyncClient::get_tip_hash`]; if that differs from the cached tip, it
+/// walks the cached history backwards, checking each height against
+/// [`AsyncClient::get_block_hash`] to find the highest height at which the
+/// server still agrees with what was cached — the common ancestor —
+/// then walks the new chain's headers back from the tip via
+/// [`AsyncClient::get_header_by_hash`] to fill in everything above it. The
+/// walk is bounded by `max_reorg_depth` so a server on a wildly divergent
+/// chain returns [`Error::ReorgTooDeep`] instead of triggering unbounded
+/// requests.
+pub struct TipPoller<S: Sleeper> {
+    client: AsyncClient<S>,
+    interval: std::time::Duration,
+    max_reorg_depth: u32,
+    /// Cached best-chain headers, oldest first; the last entry is the tip
+    /// as of the previous successful poll. Empty before the first poll.
+    history: VecDeque<BlockHeader>,
+    /// Height of `history.front()`.
+    base_height: u32,
+}
+
+impl<S: Sleeper> TipPoller<S> {
+    /// Create a poller with [`DEFAULT_MAX_REORG_DEPTH`] as its reorg-depth
+    /// bound.
+    pub fn new(client: AsyncClient<S>, interval: std::time::Duration) -> Self {
+        Self::with_max_reorg_depth(client, interval, DEFAULT_MAX_REORG_DEPTH)
+    }
+
+    /// Create a poller, overriding how many blocks it will walk back before
+    /// giving up with [`Error::ReorgTooDeep`].
+    pub fn with_max_reorg_depth(
+        client: AsyncClient<S>,
+        interval: std::time::Duration,
+        max_reorg_depth: u32,
+    ) -> Self {
+        TipPoller {
+            client,
+            interval,
+            max_reorg_depth,
+            history: VecDeque::new(),
+            base_height: 0,
+        }
+    }
+
+    /// Sleep for the configured polling interval.
+    ///
+    /// Callers driving their own loop (e.g. a `select!` alongside other
+    /// work) can ignore this and call [`TipPoller::poll`] on their own
+    /// schedule instead.
+    pub async fn tick(&self) {
+        S::sleep(self.interval).await;
+    }
+
+    /// Poll once, returning the [`ChainDifference`] needed to bring the
+    /// locally cached tip up to date with the server.
+    ///
+    /// On the very first call there is nothing cached to compare against,
+    /// so the new tip is reported as `connected` with an empty
+    /// `disconnected` list.
+    pub async fn poll(&mut self) -> Result<ChainDifference, Error> {
+        let tip_hash = self.client.get_tip_hash().await?;
+
+        if self.history.back().map(|h| h.block_hash()) == Some(tip_hash) {
+            return Ok(ChainDifference {
+                disconnected: Vec::new(),
+                connected: Vec::new(),
+            });
+        }
+
+        let tip_header = self.client.get_header_by_hash(&tip_hash).await?;
+        let tip_height = self.client.get_height().await?;
+
+        if self.history.is_empty() {
+            self.base_height = tip_height;
+            self.history.push_back(tip_header.clone());
+            return Ok(ChainDifference {
+                disconnected: Vec::new(),
+                connected: vec![tip_header],
+            });
+        }
+
+        // If the new tip is lower than the top of our cached history, every
+        // cached block strictly above it has been rolled back by
+        // definition: the new best chain doesn't even reach that height, so
+        // there's no point asking the server to confirm it.
+        let mut disconnected = drop_headers_above(&mut self.history, self.base_height, tip_height);
+
+        if self.history.is_empty() {
+            // The reorg rolled back everything we had cached; there's no
+            // overlap left to search for a common ancestor, so just
+            // re-bootstrap from the new tip like the very first poll.
+            self.base_height = tip_height;
+            self.history.push_back(tip_header.clone());
+            return Ok(ChainDifference {
+                disconnected,
+                connected: vec![tip_header],
+            });
+        }
+
+        // Find the highest height, within (or below) our cached range,
+        // where the server's best chain still agrees with what we cached.
+        let search_from = tip_height.min(self.base_height + self.history.len() as u32 - 1);
+        let (ancestor_height, mut ancestor_disconnected) = find_common_ancestor(
+            &self.history,
+            self.base_height,
+            search_from,
+            self.max_reorg_depth,
+            |height| self.client.get_block_hash(height),
+        )
+        .await?;
+        disconnected.append(&mut ancestor_disconnected);
+
+        // Walk the new best chain backwards from its tip to the common
+        // ancestor height, collecting the headers to connect.
+        let mut connected = Vec::new();
+        let mut header = tip_header;
+        let mut height = tip_height;
+        while height > ancestor_height {
+            connected.push(header.clone());
+            header = self.client.get_header_by_hash(&header.prev_blockhash).await?;
+            height -= 1;
+        }
+        connected.reverse();
+
+        // Rebuild the cache: keep everything up to the common ancestor,
+        // then append the newly connected headers.
+        self.history
+            .truncate((ancestor_height + 1 - self.base_height) as usize);
+        self.history.extend(connected.iter().cloned());
+        while self.history.len() as u32 > self.max_reorg_depth + 1 {
+            self.history.pop_front();
+            self.base_height += 1;
+        }
+
+        Ok(ChainDifference {
+            disconnected,
+            connected,
+        })
+    }
+}
+
+/// Pops cached headers whose height exceeds `tip_height` off the back of
+/// `history`, returning their hashes (highest first).
+///
+/// Used by [`TipPoller::poll`] when the new tip is shorter than the cached
+/// chain: those headers are categorically disconnected, since the new best
+/// chain doesn't even reach that height, regardless of what it agrees on
+/// further down.
+fn drop_headers_above(
+    history: &mut VecDeque<BlockHeader>,
+    base_height: u32,
+    tip_height: u32,
+) -> Vec<BlockHash> {
+    let mut disconnected = Vec::new();
+    loop {
+        let top_height = match history.len().checked_sub(1) {
+            Some(offset) => base_height + offset as u32,
+            None => break,
+        };
+        if top_height <= tip_height {
+            break;
+        }
+        if let Some(header) = history.pop_back() {
+            disconnected.push(header.block_hash());
+        }
+    }
+    disconnected
+}
+
+/// Pure common-ancestor search used by [`TipPoller::poll`].
+///
+/// `history` is the cached best-chain headers (oldest first), with
+/// `history.front()` at `base_height`. Starting at `search_from` (which must
+/// be `>= base_height`), walks backwards asking `remote_hash_at` for the
+/// server's best-chain hash at each height, until it finds one that matches
+/// the corresponding cached header — the common ancestor. Returns that
+/// height together with the hashes of every cached block above it, which
+/// have been rolled back. Bounded by `max_reorg_depth`.
+async fn find_common_ancestor<F, Fut>(
+    history: &VecDeque<BlockHeader>,
+    base_height: u32,
+    mut search_from: u32,
+    max_reorg_depth: u32,
+    remote_hash_at: F,
+) -> Result<(u32, Vec<BlockHash>), Error>
+where
+    F: Fn(u32) -> Fut,
+    Fut: std::future::Future<Output = Result<BlockHash, Error>>,
+{
+    let mut disconnected = Vec::new();
+    loop {
+        if search_from < base_height {
+            return Err(Error::ReorgTooDeep {
+                max_depth: max_reorg_depth,
+            });
+        }
+        let remote_hash = remote_hash_at(search_from).await?;
+        let cached_header = &history[(search_from - base_height) as usize];
+        if remote_hash == cached_header.block_hash() {
+            return Ok((search_from, disconnected));
+        }
+        disconnected.push(cached_header.block_hash());
+        if disconnected.len() as u32 > max_reorg_depth {
+            return Err(Error::ReorgTooDeep {
+                max_depth: max_reorg_depth,
+            });
+        }
+        search_from = search_from.checked_sub(1).ok_or(Error::ReorgTooDeep {
+            max_depth: max_reorg_depth,
+        })?;
+    }
+}
+
+/// Wraps several [`AsyncClient`] backends and requires `k` of them to agree
+/// before returning a consensus-critical read.
+///
+/// This protects against a single malicious or out-of-sync Esplora instance
+/// lying about chain state: [`QuorumClient::get_tip_hash`],
+/// [`QuorumClient::get_block_hash`], [`QuorumClient::get_header_by_hash`]
+/// and [`QuorumClient::get_block_status`] dispatch to every backend
+/// concurrently and only succeed once at least `k` backends return the same
+/// value, failing with [`Error::QuorumNotReached`] otherwise. Reads that
+/// aren't consensus-critical (fee estimates, scripthash history)
+/// instead go to the first backend, failing over to the next one on HTTP or
+/// transport errors.
+#[derive(Debug, Clone)]
+pub struct QuorumClient<S> {
+    backends: Vec<AsyncClient<S>>,
+    k: usize,
+}
+
+impl<S: Sleeper> QuorumClient<S> {
+    /// Create a quorum client requiring `k` of `backends` to agree.
+    ///
+    /// Returns [`Error::InvalidQuorumThreshold`] if `backends` is empty, or
+    /// if `k` isn't a strict majority of `backends.len()`. A non-majority
+    /// threshold (e.g. `k = 1` with several backends) would let a single
+    /// dishonest backend satisfy the quorum on its own, defeating the
+    /// malicious-backend protection this type exists to provide.
+    pub fn new(backends: Vec<AsyncClient<S>>, k: usize) -> Result<Self, Error> {
+        if backends.is_empty() || k == 0 || k > backends.len() || k <= backends.len() / 2 {
+            return Err(Error::InvalidQuorumThreshold {
+                k,
+                num_backends: backends.len(),
+            });
+        }
+        Ok(QuorumClient { backends, k })
+    }
+
+    /// Build a quorum client from a list of per-backend [`Builder`]s,
+    /// requiring `k` of them to agree.
+    pub fn from_builders(builders: Vec<Builder>, k: usize) -> Result<Self, Error> {
+        let backends = builders
+            .into_iter()
+            .map(AsyncClient::from_builder)
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(backends, k)
+    }
+
+    /// The quorum threshold `k`.
+    pub fn threshold(&self) -> usize {
+        self.k
+    }
+
+    /// The configured backends.
+    pub fn backends(&self) -> &[AsyncClient<S>] {
+        &self.backends
+    }
+
+    /// Get the chain tip hash, agreed upon by at least `k` backends.
+    pub async fn get_tip_hash(&self) -> Result<BlockHash, Error> {
+        self.quorum_read(|c| c.get_tip_hash()).await
+    }
+
+    /// Get the block hash at `height`, agreed upon by at least `k` backends.
+    pub async fn get_block_hash(&self, height: u32) -> Result<BlockHash, Error> {
+        self.quorum_read(|c| c.get_block_hash(height)).await
+    }
+
+    /// Get the header for `block_hash`, agreed upon by at least `k`
+    /// backends.
+    pub async fn get_header_by_hash(&self, block_hash: &BlockHash) -> Result<BlockHeader, Error> {
+        self.quorum_read(|c| c.get_header_by_hash(block_hash)).await
+    }
+
+    /// Get the status of `block_hash`, agreed upon by at least `k` backends.
+    pub async fn get_block_status(&self, block_hash: &BlockHash) -> Result<BlockStatus, Error> {
+        self.quorum_read(|c| c.get_block_status(block_hash)).await
+    }
+
+    /// Get fee estimates from the primary backend, failing over to the next
+    /// configured backend on HTTP or transport errors.
+    pub async fn get_fee_estimates(&self) -> Result<HashMap<u16, f64>, Error> {
+        self.read_with_failover(|c| c.get_fee_estimates()).await
+    }
+
+    /// Get confirmed transaction history for `script`'s scripthash from the
+    /// primary backend, failing over to the next configured backend on HTTP
+    /// or transport errors.
+    pub async fn scripthash_txs(
+        &self,
+        script: &Script,
+        last_seen: Option<Txid>,
+    ) -> Result<Vec<Tx>, Error> {
+        self.read_with_failover(|c| c.scripthash_txs(script, last_seen))
+            .await
+    }
+
+    /// Dispatch `f` to every backend concurrently and return the value that
+    /// at least `k` of them agree on.
+    async fn quorum_read<'c, T, F, Fut>(&'c self, f: F) -> Result<T, Error>
+    where
+        T: PartialEq + Clone,
+        F: Fn(&'c AsyncClient<S>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, Error>>,
+    {
+        let results = join_all(self.backends.iter().map(|backend| f(backend))).await;
+        tally_quorum(results, self.k)
+    }
+
+    /// Try each backend in order, returning the first success and failing
+    /// over to the next backend on HTTP or transport errors.
+    async fn read_with_failover<'c, T, F, Fut>(&'c self, f: F) -> Result<T, Error>
+    where
+        F: Fn(&'c AsyncClient<S>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, Error>>,
+    {
+        let mut last_err = None;
+        for backend in &self.backends {
+            match f(backend).await {
+                Ok(value) => return Ok(value),
+                Err(e) if is_failover_error(&e) => last_err = Some(e),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_err.unwrap_or(Error::InvalidResponse))
+    }
+}
+
+/// Pure tallying logic behind [`QuorumClient::quorum_read`]: counts how
+/// many of `results` agree on each distinct value, and returns the value
+/// with at least `k` votes, or [`Error::QuorumNotReached`] if none reaches
+/// that threshold. Errors in `results` are silently excluded from the
+/// tally, the same as a backend that simply didn't respond.
+fn tally_quorum<T: PartialEq + Clone>(
+    results: Vec<Result<T, Error>>,
+    k: usize,
+) -> Result<T, Error> {
+    let mut tally: Vec<(T, usize)> = Vec::new();
+    for result in results.into_iter().flatten() {
+        match tally.iter_mut().find(|(value, _)| *value == result) {
+            Some((_, count)) => *count += 1,
+            None => tally.push((result, 1)),
+        }
+    }
+
+    match tally.iter().find(|(_, count)| *count >= k) {
+        Some((value, _)) => Ok(value.clone()),
+        None => Err(Error::QuorumNotReached {
+            required: k,
+            divergent: tally.len(),
+        }),
+    }
+}
+
+/// Whether an error from a [`QuorumClient`] backend should trigger
+/// failover to the next backend in [`QuorumClient::read_with_failover`],
+/// rather than aborting the read.
+fn is_failover_error(err: &Error) -> bool {
+    matches!(err, Error::HttpResponse { .. } | Error::AsyncMinreq(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::block::Version;
+    use bitcoin::hash_types::TxMerkleNode;
+    use bitcoin::pow::CompactTarget;
+
+    fn test_header(nonce: u32, prev_blockhash: BlockHash) -> BlockHeader {
+        BlockHeader {
+            version: Version::ONE,
+            prev_blockhash,
+            merkle_root: TxMerkleNode::all_zeros(),
+            time: 0,
+            bits: CompactTarget::from_consensus(0),
+            nonce,
+        }
+    }
+
+    fn test_txid(seed: u8) -> Txid {
+        Txid::hash(&[seed])
+    }
+
+    fn test_tx(txid: Txid, confirmed: bool) -> Tx {
+        Tx {
+            txid,
+            version: 1,
+            locktime: 0,
+            size: 0,
+            weight: 0,
+            fee: 0,
+            status: TxStatus {
+                confirmed,
+                block_height: None,
+                block_hash: None,
+                block_time: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn paginated_tx_stream_filters_unconfirmed_and_stops_on_short_page() {
+        let confirmed_a = test_txid(1);
+        let confirmed_b = test_txid(2);
+        let page = vec![
+            test_tx(test_txid(0), false),
+            test_tx(confirmed_a, true),
+            test_tx(confirmed_b, true),
+        ];
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(0));
+        let calls_inner = calls.clone();
+
+        let results: Vec<Tx> = paginated_tx_stream(move |_last_seen| {
+            *calls_inner.borrow_mut() += 1;
+            let page = page.clone();
+            async move { Ok(page) }
+        })
+        .map(|r| r.unwrap())
+        .collect()
+        .await;
+
+        assert_eq!(
+            results.into_iter().map(|tx| tx.txid).collect::<Vec<_>>(),
+            vec![confirmed_a, confirmed_b]
+        );
+        assert_eq!(*calls.borrow(), 1);
+    }
+
+    #[tokio::test]
+    async fn paginated_tx_stream_paginates_using_last_confirmed_cursor() {
+        let first_page: Vec<Tx> = (0..CONFIRMED_TXS_PAGE_SIZE as u8)
+            .map(|n| test_tx(test_txid(n), true))
+            .collect();
+        let last_of_first_page = first_page.last().unwrap().txid;
+        let second_page = vec![test_tx(test_txid(200), true)];
+
+        let seen_cursors = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let seen_cursors_inner = seen_cursors.clone();
+
+        let results: Vec<Tx> = paginated_tx_stream(move |last_seen| {
+            seen_cursors_inner.borrow_mut().push(last_seen);
+            let page = if last_seen.is_none() {
+                first_page.clone()
+            } else {
+                second_page.clone()
+            };
+            async move { Ok(page) }
+        })
+        .map(|r| r.unwrap())
+        .collect()
+        .await;
+
+        assert_eq!(results.len(), CONFIRMED_TXS_PAGE_SIZE + 1);
+        assert_eq!(
+            seen_cursors.borrow().clone(),
+            vec![None, Some(last_of_first_page)]
+        );
+    }
+
+    #[tokio::test]
+    async fn find_common_ancestor_no_reorg() {
+        let genesis = test_header(0, BlockHash::all_zeros());
+        let block1 = test_header(1, genesis.block_hash());
+        let history = VecDeque::from(vec![genesis.clone(), block1.clone()]);
+
+        let (height, disconnected) =
+            find_common_ancestor(&history, 0, 1, DEFAULT_MAX_REORG_DEPTH, |h| {
+                let hash = if h == 0 {
+                    genesis.block_hash()
+                } else {
+                    block1.block_hash()
+                };
+                async move { Ok(hash) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(height, 1);
+        assert!(disconnected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_common_ancestor_one_block_reorg() {
+        let genesis = test_header(0, BlockHash::all_zeros());
+        let old_tip = test_header(1, genesis.block_hash());
+        let new_tip = test_header(2, genesis.block_hash());
+        assert_ne!(old_tip.block_hash(), new_tip.block_hash());
+
+        let history = VecDeque::from(vec![genesis.clone(), old_tip.clone()]);
+
+        let (height, disconnected) =
+            find_common_ancestor(&history, 0, 1, DEFAULT_MAX_REORG_DEPTH, |h| {
+                let hash = if h == 0 {
+                    genesis.block_hash()
+                } else {
+                    new_tip.block_hash()
+                };
+                async move { Ok(hash) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(height, 0);
+        assert_eq!(disconnected, vec![old_tip.block_hash()]);
+    }
+
+    #[tokio::test]
+    async fn find_common_ancestor_bounded_by_max_depth() {
+        let genesis = test_header(0, BlockHash::all_zeros());
+        let old_tip = test_header(1, genesis.block_hash());
+        let diverged = test_header(99, genesis.block_hash());
+
+        let history = VecDeque::from(vec![genesis.clone(), old_tip.clone()]);
+
+        let result = find_common_ancestor(&history, 0, 1, 0, |_| {
+            let hash = diverged.block_hash();
+            async move { Ok(hash) }
+        })
+        .await;
+
+        assert!(matches!(result, Err(Error::ReorgTooDeep { max_depth: 0 })));
+    }
+
+    #[test]
+    fn drop_headers_above_removes_only_the_tip_entries() {
+        let genesis = test_header(0, BlockHash::all_zeros());
+        let block1 = test_header(1, genesis.block_hash());
+        let block2 = test_header(2, block1.block_hash());
+        let mut history = VecDeque::from(vec![genesis.clone(), block1.clone(), block2.clone()]);
+
+        // A one-block shrinking reorg: the new tip is one height below the
+        // top of our cached history.
+        let disconnected = drop_headers_above(&mut history, 0, 1);
+
+        assert_eq!(disconnected, vec![block2.block_hash()]);
+        assert_eq!(history, VecDeque::from(vec![genesis, block1]));
+    }
+
+    #[test]
+    fn drop_headers_above_empties_history_when_tip_is_below_base_height() {
+        let genesis = test_header(0, BlockHash::all_zeros());
+        let mut history = VecDeque::from(vec![genesis.clone()]);
+
+        // The new tip is shorter than even our earliest cached height: there
+        // is no overlap left, so every cached header is disconnected.
+        let disconnected = drop_headers_above(&mut history, 5, 4);
+
+        assert_eq!(disconnected, vec![genesis.block_hash()]);
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn retry_policy_should_retry_respects_max_attempts() {
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            ..RetryPolicy::default()
+        };
+        assert!(policy.should_retry(0, 503, std::time::Duration::ZERO));
+        assert!(policy.should_retry(1, 503, std::time::Duration::ZERO));
+        assert!(!policy.should_retry(2, 503, std::time::Duration::ZERO));
+    }
+
+    #[test]
+    fn retry_policy_should_retry_only_retryable_codes() {
+        let policy = RetryPolicy::default();
+        assert!(!policy.should_retry(0, 404, std::time::Duration::ZERO));
+        assert!(policy.should_retry(0, 503, std::time::Duration::ZERO));
+    }
+
+    #[test]
+    fn retry_policy_should_retry_respects_max_elapsed() {
+        let policy = RetryPolicy {
+            max_elapsed: Some(std::time::Duration::from_secs(1)),
+            ..RetryPolicy::default()
+        };
+        assert!(policy.should_retry(0, 503, std::time::Duration::from_millis(500)));
+        assert!(!policy.should_retry(0, 503, std::time::Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn retry_policy_delay_for_attempt_backs_off_and_caps() {
+        let policy = RetryPolicy {
+            base_backoff: std::time::Duration::from_millis(100),
+            multiplier: 2.0,
+            max_backoff: std::time::Duration::from_millis(350),
+            jitter: std::time::Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(
+            policy.delay_for_attempt(0),
+            std::time::Duration::from_millis(100)
+        );
+        assert_eq!(
+            policy.delay_for_attempt(1),
+            std::time::Duration::from_millis(200)
+        );
+        // Would be 400ms uncapped; max_backoff caps it at 350ms.
+        assert_eq!(
+            policy.delay_for_attempt(2),
+            std::time::Duration::from_millis(350)
+        );
+    }
+
+    #[test]
+    fn quorum_new_rejects_empty_backends() {
+        assert!(matches!(
+            QuorumClient::<DefaultSleeper>::new(Vec::new(), 1),
+            Err(Error::InvalidQuorumThreshold { k: 1, num_backends: 0 })
+        ));
+    }
+
+    #[test]
+    fn tally_quorum_returns_value_with_enough_votes() {
+        let results: Vec<Result<u32, Error>> = vec![Ok(1), Ok(1), Ok(2)];
+        assert_eq!(tally_quorum(results, 2).unwrap(), 1);
+    }
+
+    #[test]
+    fn tally_quorum_fails_when_no_value_reaches_threshold() {
+        let results: Vec<Result<u32, Error>> = vec![Ok(1), Ok(2), Ok(3)];
+        assert!(matches!(
+            tally_quorum(results, 2),
+            Err(Error::QuorumNotReached {
+                required: 2,
+                divergent: 3,
+            })
+        ));
+    }
+
+    #[test]
+    fn tally_quorum_ignores_errors_in_the_tally() {
+        let results: Vec<Result<u32, Error>> =
+            vec![Ok(1), Ok(1), Err(Error::Timeout), Err(Error::Timeout)];
+        assert_eq!(tally_quorum(results, 2).unwrap(), 1);
+    }
+
+    #[test]
+    fn is_failover_error_true_for_http_errors() {
+        assert!(is_failover_error(&Error::HttpResponse {
+            status: 503,
+            message: "unavailable".to_string(),
+        }));
+    }
+
+    #[test]
+    fn is_failover_error_false_for_other_errors() {
+        assert!(!is_failover_error(&Error::Timeout));
+        assert!(!is_failover_error(&Error::InvalidResponse));
+    }
+
+    #[test]
+    fn pick_fee_estimate_returns_exact_match() {
+        let estimates = HashMap::from([(1, 10.0), (6, 5.0), (144, 1.0)]);
+        assert_eq!(pick_fee_estimate(&estimates, 6), Some(5.0));
+    }
+
+    #[test]
+    fn pick_fee_estimate_rounds_up_to_next_higher_target() {
+        let estimates = HashMap::from([(1, 10.0), (6, 5.0), (144, 1.0)]);
+        // No published estimate for target 3; the next higher target (6) is used.
+        assert_eq!(pick_fee_estimate(&estimates, 3), Some(5.0));
+    }
+
+    #[test]
+    fn pick_fee_estimate_falls_back_to_slowest_when_target_exceeds_all() {
+        let estimates = HashMap::from([(1, 10.0), (6, 5.0), (144, 1.0)]);
+        assert_eq!(pick_fee_estimate(&estimates, 1000), Some(1.0));
+    }
+
+    #[test]
+    fn pick_fee_estimate_none_when_no_estimates() {
+        let estimates: HashMap<u16, f64> = HashMap::new();
+        assert_eq!(pick_fee_estimate(&estimates, 6), None);
+    }
+
+    #[test]
+    fn is_already_known_response_matches_known_phrasings() {
+        assert!(is_already_known_response(
+            "transaction already in block chain"
+        ));
+        assert!(is_already_known_response(
+            "txn-already-known: already have transaction in mempool"
+        ));
+        assert!(is_already_known_response("Already Known"));
+    }
+
+    #[test]
+    fn is_already_known_response_false_for_unrelated_errors() {
+        assert!(!is_already_known_response("bad-txns-inputs-missing"));
+        assert!(!is_already_known_response("already"));
+        assert!(!is_already_known_response("mempool min fee not met"));
+    }
+}
@@ -0,0 +1,185 @@
+// Bitcoin Dev Kit
+// Written in 2020 by Alekos Filini <alekos.filini@gmail.com>
+//
+// Copyright (c) 2020-2021 Bitcoin Dev Kit Developers
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+//! A client for the Esplora HTTP API, used by Bitcoin wallets to query
+//! chain state and broadcast transactions.
+
+use std::collections::HashMap;
+
+pub mod api;
+pub mod r#async;
+
+pub use api::*;
+pub use r#async::AsyncClient;
+
+/// Base delay used before the first retry of a failed request.
+pub const BASE_BACKOFF_MILLIS: std::time::Duration = std::time::Duration::from_millis(256);
+
+/// Default maximum number of retries for a failed request.
+pub const DEFAULT_MAX_RETRIES: usize = 3;
+
+/// HTTP status codes considered transient and worth retrying.
+pub const RETRYABLE_ERROR_CODES: [u16; 4] = [429, 500, 503, 504];
+
+/// Builds an Esplora client, configuring the base URL, default headers and
+/// retry behavior shared across requests.
+#[derive(Debug, Clone)]
+pub struct Builder {
+    /// The base URL of the Esplora server, without a trailing slash.
+    pub base_url: String,
+    /// Default headers applied to every request.
+    pub headers: HashMap<String, String>,
+    /// Policy governing how failed requests are retried.
+    pub retry_policy: r#async::RetryPolicy,
+}
+
+impl Builder {
+    /// Create a new builder targeting `base_url`, with no default headers
+    /// and the default [`r#async::RetryPolicy`].
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Builder {
+            base_url: base_url.into(),
+            headers: HashMap::new(),
+            retry_policy: r#async::RetryPolicy::default(),
+        }
+    }
+
+    /// Add a default header applied to every request.
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the retry policy used for failed requests.
+    pub fn retry_policy(mut self, retry_policy: r#async::RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set the maximum number of retries, leaving the rest of the retry
+    /// policy unchanged. Kept for callers that only want to tweak the retry
+    /// count without building a full [`r#async::RetryPolicy`].
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.retry_policy.max_attempts = max_retries;
+        self
+    }
+
+    /// Build an [`r#async::AsyncClient`] from this builder.
+    pub fn build_async<S: r#async::Sleeper>(self) -> Result<r#async::AsyncClient<S>, Error> {
+        r#async::AsyncClient::from_builder(self)
+    }
+
+    /// Build a [`r#async::QuorumClient`] that dispatches consensus-critical
+    /// reads across `backends`, requiring `k` of them to agree.
+    pub fn build_quorum_async<S: r#async::Sleeper>(
+        backends: Vec<Builder>,
+        k: usize,
+    ) -> Result<r#async::QuorumClient<S>, Error> {
+        r#async::QuorumClient::from_builders(backends, k)
+    }
+}
+
+/// Errors that can occur while using the Esplora clients in this crate.
+#[derive(Debug)]
+pub enum Error {
+    /// Error during an async HTTP request.
+    AsyncMinreq(async_minreq::Error),
+    /// The HTTP response had a status code indicating failure.
+    HttpResponse { status: u16, message: String },
+    /// Error decoding a hex string into a fixed-size array (e.g. a txid or
+    /// block hash).
+    HexToArray(bitcoin::hex::HexToArrayError),
+    /// Error decoding a hex string into bytes.
+    HexToBytes(bitcoin::hex::HexToBytesError),
+    /// Error parsing an integer out of a text response.
+    Parsing(std::num::ParseIntError),
+    /// Error decoding a native `rust-bitcoin` type from consensus-encoded
+    /// bytes.
+    BitcoinEncoding(bitcoin::consensus::encode::Error),
+    /// Error decoding a JSON response.
+    Json(serde_json::Error),
+    /// The response body wasn't in the shape the caller expected.
+    InvalidResponse,
+    /// The requested transaction could not be found.
+    TransactionNotFound(bitcoin::Txid),
+    /// A [`r#async::TipPoller`] would have had to walk back more than
+    /// `max_depth` blocks to find the common ancestor of a reorg.
+    ReorgTooDeep { max_depth: u32 },
+    /// A transaction that was previously seen in the mempool disappeared
+    /// without ever confirming.
+    TransactionEvicted(bitcoin::Txid),
+    /// Polling timed out before the requested condition was met.
+    Timeout,
+    /// Fewer than the required number of [`r#async::QuorumClient`] backends
+    /// agreed on a consensus-critical read.
+    QuorumNotReached { required: usize, divergent: usize },
+    /// A [`r#async::QuorumClient`] was configured with an invalid quorum
+    /// threshold or backend list.
+    InvalidQuorumThreshold { k: usize, num_backends: usize },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::AsyncMinreq(e) => write!(f, "HTTP transport error: {e}"),
+            Error::HttpResponse { status, message } => {
+                write!(f, "HTTP error {status}: {message}")
+            }
+            Error::HexToArray(e) => write!(f, "invalid hex array: {e}"),
+            Error::HexToBytes(e) => write!(f, "invalid hex bytes: {e}"),
+            Error::Parsing(e) => write!(f, "failed to parse integer: {e}"),
+            Error::BitcoinEncoding(e) => write!(f, "failed to decode bitcoin data: {e}"),
+            Error::Json(e) => write!(f, "failed to decode JSON: {e}"),
+            Error::InvalidResponse => write!(f, "the server returned an invalid response"),
+            Error::TransactionNotFound(txid) => write!(f, "transaction {txid} not found"),
+            Error::ReorgTooDeep { max_depth } => write!(
+                f,
+                "reorg deeper than the configured maximum of {max_depth} blocks"
+            ),
+            Error::TransactionEvicted(txid) => write!(
+                f,
+                "transaction {txid} was evicted from the mempool before confirming"
+            ),
+            Error::Timeout => write!(f, "timed out waiting for the requested condition"),
+            Error::QuorumNotReached {
+                required,
+                divergent,
+            } => write!(
+                f,
+                "quorum of {required} backends not reached ({divergent} divergent responses)"
+            ),
+            Error::InvalidQuorumThreshold { k, num_backends } => write!(
+                f,
+                "invalid quorum threshold {k} for {num_backends} backend(s)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<async_minreq::Error> for Error {
+    fn from(e: async_minreq::Error) -> Self {
+        Error::AsyncMinreq(e)
+    }
+}
+
+impl From<bitcoin::consensus::encode::Error> for Error {
+    fn from(e: bitcoin::consensus::encode::Error) -> Self {
+        Error::BitcoinEncoding(e)
+    }
+}
+
+impl From<bitcoin::hex::HexToBytesError> for Error {
+    fn from(e: bitcoin::hex::HexToBytesError) -> Self {
+        Error::HexToBytes(e)
+    }
+}